@@ -0,0 +1,50 @@
+//! Async surface over the blocking, GIL-bound calls.
+//!
+//! Every `Device` call blocks the calling thread while holding the GIL,
+//! which makes driving many devices concurrently impossible from a single
+//! thread. These methods offload each blocking call onto Tokio's blocking
+//! thread pool via `spawn_blocking`, so the GIL is only ever held by
+//! whichever blocking-pool thread is running a given call, and the
+//! returned futures can be `join!`-ed across a whole fleet of devices.
+
+use serde::de::DeserializeOwned;
+
+use crate::{Device, MiioError};
+
+impl Device {
+    /// Async counterpart to [`Device::call`].
+    pub async fn call_async<R>(
+        &self,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<R, MiioError>
+    where
+        R: DeserializeOwned + Send + 'static,
+    {
+        let device = self.clone();
+        let method = method.to_string();
+        let args = args.to_vec();
+        tokio::task::spawn_blocking(move || device.call(&method, &args)).await?
+    }
+
+    /// Async counterpart to [`Device::create_device`].
+    pub async fn create_device_async(
+        ip: &str,
+        token: &str,
+        device_type: &str,
+    ) -> Result<Device, MiioError> {
+        let ip = ip.to_string();
+        let token = token.to_string();
+        let device_type = device_type.to_string();
+        let device =
+            tokio::task::spawn_blocking(move || Device::create_device(&ip, &token, &device_type))
+                .await??;
+        Ok(device)
+    }
+}
+
+/// Async counterpart to [`crate::get_device_types`].
+pub async fn get_device_types_async() -> Result<Vec<String>, MiioError> {
+    let device_types = tokio::task::spawn_blocking(crate::get_device_types).await??;
+    Ok(device_types)
+}
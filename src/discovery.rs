@@ -0,0 +1,239 @@
+//! Local-network discovery for Miio devices.
+//!
+//! Implements the python-miio "handshake" used to find devices before their
+//! `token`/`device_type` are known: a UDP broadcast to port 54321 whose
+//! response carries the device id and uptime stamp, but no further detail.
+//! `DeviceBuilder` drives that handshake against a subnet and turns every
+//! responder it can instantiate into a `Device`.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use crate::{Device, PythonTransport};
+
+/// Port python-miio devices listen on for the discovery handshake.
+const DISCOVERY_PORT: u16 = 54321;
+
+/// The fixed 32-byte "hello" packet that triggers a handshake response.
+const HELLO_PACKET: [u8; 32] = [
+    0x21, 0x31, 0x00, 0x20, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+];
+
+/// A device that answered the discovery handshake but has not yet been
+/// turned into a `Device` (its `device_type` may still be unknown).
+///
+/// `device_id`/`stamp` come straight off the handshake response and exist
+/// to collapse repeat responders in `broadcast()`: the same device can
+/// answer more than once while the scan is running, and `device_id` is the
+/// only thing in the response that identifies it, with `stamp` picking the
+/// freshest of its responses.
+#[derive(Debug, Clone)]
+struct Candidate {
+    ip: String,
+    device_id: u32,
+    stamp: u32,
+}
+
+/// Builds a `Vec<Device>` by probing the local network for responders.
+///
+/// Configure the scan with `with_token`, `with_subnet` and `with_timeout`,
+/// then call `probe()`. This mirrors the candidate-pool-then-probe pattern
+/// used for discovering devices whose address isn't known up front: collect
+/// everyone who answers, then instantiate only the ones that check out.
+pub struct DeviceBuilder {
+    token: Option<String>,
+    subnet: Ipv4Addr,
+    prefix_len: u8,
+    timeout: Duration,
+    device_type: Option<String>,
+}
+
+impl DeviceBuilder {
+    /// Creates a builder that scans `192.168.1.0/24` with a 2 second timeout
+    /// and no token; `probe()` fails with `InvalidInput`, without sending
+    /// anything, until a token is set with `with_token`.
+    pub fn new() -> Self {
+        DeviceBuilder {
+            token: None,
+            subnet: Ipv4Addr::new(192, 168, 1, 0),
+            prefix_len: 24,
+            timeout: Duration::from_secs(2),
+            device_type: None,
+        }
+    }
+
+    /// Sets the token used to instantiate every responding device.
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Sets the subnet to scan, as a CIDR string such as `"192.168.1.0/24"`.
+    ///
+    /// Falls back to the previously configured subnet if `subnet` cannot be
+    /// parsed.
+    pub fn with_subnet(mut self, subnet: &str) -> Self {
+        if let Some((addr, prefix)) = subnet.split_once('/') {
+            if let (Ok(addr), Ok(prefix)) = (addr.parse::<Ipv4Addr>(), prefix.parse::<u8>()) {
+                self.subnet = addr;
+                self.prefix_len = prefix;
+            }
+        }
+        self
+    }
+
+    /// Sets how long to wait for handshake responses after the broadcast.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Forces every responder to be instantiated as this `device_type`
+    /// instead of auto-resolving it by calling `info()`.
+    pub fn with_device_type(mut self, device_type: &str) -> Self {
+        self.device_type = Some(device_type.to_string());
+        self
+    }
+
+    /// Broadcasts the handshake, collects responders, and instantiates a
+    /// `Device` for every one that answers and can be created successfully.
+    ///
+    /// Devices that respond but fail `Device::create_device` (wrong token,
+    /// unresolvable `device_type`, ...) are silently skipped rather than
+    /// failing the whole scan.
+    pub fn probe(&self) -> Result<Vec<Device>, std::io::Error> {
+        let Some(token) = self.token.as_deref() else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "DeviceBuilder::probe requires a token, see with_token",
+            ));
+        };
+
+        let candidates = self.broadcast()?;
+        let mut devices = Vec::new();
+        for candidate in candidates {
+            let device_type = match &self.device_type {
+                Some(device_type) => device_type.clone(),
+                None => match Self::resolve_device_type(&candidate.ip, token) {
+                    Some(device_type) => device_type,
+                    None => continue,
+                },
+            };
+
+            if let Ok(device) = Device::create_device(&candidate.ip, token, &device_type) {
+                devices.push(device);
+            }
+        }
+        Ok(devices)
+    }
+
+    /// Sends the hello packet as a subnet broadcast and collects every
+    /// distinct responder (by `device_id`) until `self.timeout` elapses in
+    /// total, regardless of how many responses arrive along the way.
+    fn broadcast(&self) -> Result<Vec<Candidate>, std::io::Error> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.set_broadcast(true)?;
+
+        let broadcast_addr = SocketAddr::new(IpAddr::V4(self.broadcast_ip()), DISCOVERY_PORT);
+        socket.send_to(&HELLO_PACKET, broadcast_addr)?;
+
+        let deadline = Instant::now() + self.timeout;
+        let mut candidates: HashMap<u32, Candidate> = HashMap::new();
+        let mut buf = [0u8; 32];
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            socket.set_read_timeout(Some(remaining))?;
+
+            match socket.recv_from(&mut buf) {
+                Ok((32, from)) => {
+                    let device_id = u32::from_be_bytes(buf[8..12].try_into().unwrap());
+                    let stamp = u32::from_be_bytes(buf[12..16].try_into().unwrap());
+                    let candidate = Candidate {
+                        ip: from.ip().to_string(),
+                        device_id,
+                        stamp,
+                    };
+                    candidates
+                        .entry(device_id)
+                        .and_modify(|existing| {
+                            if stamp > existing.stamp {
+                                *existing = candidate.clone();
+                            }
+                        })
+                        .or_insert(candidate);
+                }
+                Ok(_) => continue,
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(candidates.into_values().collect())
+    }
+
+    /// Derives the broadcast address for `self.subnet`/`self.prefix_len`.
+    fn broadcast_ip(&self) -> Ipv4Addr {
+        let mask: u32 = if self.prefix_len >= 32 {
+            u32::MAX
+        } else {
+            !0u32 >> self.prefix_len ^ u32::MAX
+        };
+        let base = u32::from(self.subnet);
+        Ipv4Addr::from(base | !mask)
+    }
+
+    /// Calls `info()` on a responder to learn its miIO model id, then maps
+    /// that model to the `device_type` class name `Device::create_device`
+    /// expects, returning `None` if the device can't be reached with
+    /// `token` or the model isn't recognized.
+    ///
+    /// `info()` identifies itself generically; python-miio's own `Device`
+    /// base class is the only type that can be instantiated without
+    /// already knowing the concrete model.
+    fn resolve_device_type(ip: &str, token: &str) -> Option<String> {
+        let device = Device::create_device(ip, token, "Device").ok()?;
+        let info: serde_json::Value = device.call("info", &[]).ok()?;
+        let model = info.get("model")?.as_str()?;
+        PythonTransport::resolve_device_type(model).ok()?
+    }
+}
+
+impl Default for DeviceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_ip_default_subnet() {
+        let builder = DeviceBuilder::new();
+        assert_eq!(builder.broadcast_ip(), Ipv4Addr::new(192, 168, 1, 255));
+    }
+
+    #[test]
+    fn test_broadcast_ip_with_subnet() {
+        let builder = DeviceBuilder::new().with_subnet("10.0.0.0/24");
+        assert_eq!(builder.broadcast_ip(), Ipv4Addr::new(10, 0, 0, 255));
+    }
+
+    #[test]
+    fn test_broadcast_ip_with_narrower_subnet() {
+        let builder = DeviceBuilder::new().with_subnet("10.0.0.0/30");
+        assert_eq!(builder.broadcast_ip(), Ipv4Addr::new(10, 0, 0, 3));
+    }
+
+    #[test]
+    fn test_with_subnet_invalid_keeps_previous() {
+        let builder = DeviceBuilder::new().with_subnet("not-a-subnet");
+        assert_eq!(builder.broadcast_ip(), Ipv4Addr::new(192, 168, 1, 255));
+    }
+}
@@ -0,0 +1,60 @@
+//! Error type returned by the typed device call API.
+
+use pyo3::PyErr;
+
+/// Errors that can occur while talking to a Miio device through the
+/// embedded Python interpreter.
+#[derive(Debug)]
+pub enum MiioError {
+    /// The embedded Python interpreter raised an exception.
+    Python(PyErr),
+    /// A call argument or result could not be converted to/from JSON.
+    Json(serde_json::Error),
+    /// The blocking-pool task running a call panicked or was cancelled.
+    Join(tokio::task::JoinError),
+    /// The active `Transport` doesn't implement the requested operation.
+    Unsupported(&'static str),
+}
+
+impl std::fmt::Display for MiioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MiioError::Python(e) => write!(f, "python error: {e}"),
+            MiioError::Json(e) => write!(f, "json error: {e}"),
+            MiioError::Join(e) => write!(f, "async task error: {e}"),
+            MiioError::Unsupported(msg) => write!(f, "unsupported: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for MiioError {}
+
+impl From<PyErr> for MiioError {
+    fn from(e: PyErr) -> Self {
+        MiioError::Python(e)
+    }
+}
+
+impl From<serde_json::Error> for MiioError {
+    fn from(e: serde_json::Error) -> Self {
+        MiioError::Json(e)
+    }
+}
+
+impl From<tokio::task::JoinError> for MiioError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        MiioError::Join(e)
+    }
+}
+
+impl MiioError {
+    /// Converts into a `PyErr`, wrapping every non-`Python` error (`Json`,
+    /// `Join`, `Unsupported`) as a Python `RuntimeError` so call sites that
+    /// still expect a `PyErr` keep working.
+    pub fn into_py_err(self) -> PyErr {
+        match self {
+            MiioError::Python(e) => e,
+            other => pyo3::exceptions::PyRuntimeError::new_err(other.to_string()),
+        }
+    }
+}
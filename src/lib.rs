@@ -2,17 +2,42 @@
 //!
 //! It offers functions to retrieve available device types, create devices, and call device methods.
 //! Devices are represented by the Device struct which supports serialization and deserialization.
+//! [`DeviceBuilder`] additionally discovers devices on the local network without requiring their
+//! IP address to already be known. `_async` counterparts (e.g. [`Device::call_async`]) run the
+//! same GIL-bound calls on a Tokio blocking thread for concurrent use. Device I/O itself goes
+//! through a pluggable [`Transport`]; [`PythonTransport`] is the only stable implementation today.
 
-use std::collections::HashMap;
-use std::ffi::CString;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyDict, PyModule};
+#[cfg(test)]
+use pyo3::types::PyModule;
 
-use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json;
 
+mod async_api;
 mod constants;
+mod discovery;
+mod error;
+#[cfg(feature = "unstable")]
+mod native_transport;
+mod python_transport;
+mod runtime;
+mod transport;
+
+pub use async_api::get_device_types_async;
+pub use discovery::DeviceBuilder;
+pub use error::MiioError;
+#[cfg(feature = "unstable")]
+pub use native_transport::NativeTransport;
+pub use python_transport::PythonTransport;
+pub use runtime::MiioRuntime;
+pub use transport::Transport;
 
 const MIIO_INTERFACE_CODE: &str = include_str!("../python-src/miio_interface.py");
 
@@ -24,30 +49,14 @@ const MIIO_INTERFACE_CODE: &str = include_str!("../python-src/miio_interface.py"
 /// * `Err(PyErr)` - An error if the Python call fails.
 
 pub fn get_device_types() -> Result<Vec<String>, PyErr> {
-    Python::with_gil(|py| {
-        // Import the Python module
-        let miio_module = PyModule::from_code(
-            py,
-            CString::new(MIIO_INTERFACE_CODE)?.as_c_str(),
-            &CString::new("miio_interface.py")?,
-            &CString::new("miio_interface")?,
-        )?;
-
-        // Retrieve the Python function 'get_device_types'
-        let get_device_types = miio_module.getattr("get_device_types")?;
-        // Call the function without arguments
-        let device_types_py = get_device_types.call0()?;
-        // Convert Python list to Rust Vec<String>
-        let v: Vec<String> = device_types_py.extract()?;
-        Ok(v)
-    })
+    PythonTransport::list_types().map_err(MiioError::into_py_err)
 }
 
-/// Represents a Miio device with its associated properties and Python object.
+/// Represents a Miio device with its associated properties and backing `Transport`.
 ///
 /// The Device struct includes data necessary for device communication and method invocation,
 /// along with functionalities to serialize/deserialize the device configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone)]
 pub struct Device {
     /// The type of the device.
     device_type: String,
@@ -55,12 +64,69 @@ pub struct Device {
     ip: String,
     /// The token used for device authentication.
     token: String,
-    /// A serialized representation of the underlying Python object as bytes.
+    /// The backend this device talks through.
+    transport: Box<dyn Transport>,
+    /// Bounded history of `(method, args)` calls, replayed by `reconnect` to
+    /// restore on-device state. Never grows past `history_cap`, which is 0
+    /// (history disabled) unless `with_history` is used.
+    history: Arc<Mutex<VecDeque<(String, Vec<serde_json::Value>)>>>,
+    history_cap: usize,
+}
+
+impl std::fmt::Debug for Device {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Device")
+            .field("device_type", &self.device_type)
+            .field("ip", &self.ip)
+            .field("token", &self.token)
+            .field("callable_methods", &self.transport.callable_methods())
+            .finish()
+    }
+}
+
+/// Serializes a `Device` to the same JSON shape used before the `Transport`
+/// abstraction existed, so persisted files stay readable across the change.
+/// Deserializing always rebuilds the persisted state behind a
+/// [`PythonTransport`]: the `unstable` native backend doesn't support
+/// persistence yet.
+impl Serialize for Device {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Device", 5)?;
+        state.serialize_field("device_type", &self.device_type)?;
+        state.serialize_field("ip", &self.ip)?;
+        state.serialize_field("token", &self.token)?;
+        state.serialize_field("serialized_py_object", &self.transport.state())?;
+        state.serialize_field("callable_methods", &self.transport.callable_methods())?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct DeviceFields {
+    device_type: String,
+    ip: String,
+    token: String,
     serialized_py_object: Vec<u8>,
-    /// A map of callable method names to their corresponding Python signatures.
     callable_methods: HashMap<String, String>,
 }
 
+impl<'de> Deserialize<'de> for Device {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = DeviceFields::deserialize(deserializer)?;
+        Ok(Device {
+            device_type: fields.device_type,
+            ip: fields.ip,
+            token: fields.token,
+            transport: Box::new(PythonTransport::from_state(
+                fields.serialized_py_object,
+                fields.callable_methods,
+            )),
+            history: Arc::new(Mutex::new(VecDeque::new())),
+            history_cap: 0,
+        })
+    }
+}
+
 impl Device {
     /// Serializes the Device instance to a JSON file.
     ///
@@ -97,10 +163,8 @@ impl Device {
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
     }
 
-    /// Creates a new Device instance by invoking the Python function.
-    ///
-    /// This function calls the Python module to create a device and retrieve its properties,
-    /// including serialized state and callable methods.
+    /// Creates a new Device instance backed by [`PythonTransport`], the
+    /// stable backend.
     ///
     /// # Arguments
     ///
@@ -113,49 +177,154 @@ impl Device {
     /// * `Ok(Device)` on success.
     /// * `Err(PyErr)` if any Python call fails.
     pub fn create_device(ip: &str, token: &str, device_type: &str) -> Result<Device, PyErr> {
-        Python::with_gil(|py| {
-            // Import the Python module
-            let miio_module = PyModule::from_code(
-                py,
-                CString::new(MIIO_INTERFACE_CODE)?.as_c_str(),
-                &CString::new("miio_interface.py")?,
-                &CString::new("miio_interface")?,
-            )?;
-
-            // Retrieve the Python function 'create_device'
-            let create_device = miio_module.getattr("get_device")?;
-            // Call the function with arguments
-            let device: Bound<'_, PyBytes> = create_device
-                .call1((ip, token, device_type))?
-                .downcast::<PyBytes>()?
-                .clone();
-
-            // Retrieve the Python function 'get_device_methods'
-            let get_device_methods = miio_module.getattr("get_device_methods")?;
-            // Call the function with arguments
-            let methods = get_device_methods.call1((device.clone(),))?; // Dict returned
-            let methods = methods.downcast::<PyDict>()?;
-            let mut callable_methods = HashMap::new();
-            for (key, value) in methods.iter() {
-                let key = key.extract::<String>()?;
-                let value = value.extract::<String>()?;
-                callable_methods.insert(key, value);
-            }
+        Self::create_device_with::<PythonTransport>(ip, token, device_type)
+            .map_err(MiioError::into_py_err)
+    }
 
-            let device_bytes = device.as_bytes().to_vec();
-            Ok(Device {
-                device_type: device_type.to_string(),
-                ip: ip.to_string(),
-                token: token.to_string(),
-                serialized_py_object: device_bytes,
-                callable_methods,
-            })
+    /// Creates a new Device instance backed by a specific `Transport`
+    /// implementation, letting callers pick a backend (e.g. the `unstable`
+    /// `NativeTransport`) without changing any other call site.
+    ///
+    /// # Arguments
+    ///
+    /// * `ip` - The IP address of the device.
+    /// * `token` - The token used for authentication.
+    /// * `device_type` - The type of the device.
+    pub fn create_device_with<T: Transport + 'static>(
+        ip: &str,
+        token: &str,
+        device_type: &str,
+    ) -> Result<Device, MiioError> {
+        let transport = T::create(ip, token, device_type)?;
+        Ok(Device {
+            device_type: device_type.to_string(),
+            ip: ip.to_string(),
+            token: token.to_string(),
+            transport: Box::new(transport),
+            history: Arc::new(Mutex::new(VecDeque::new())),
+            history_cap: 0,
         })
     }
 
+    /// Returns the callable methods this device exposes.
+    pub fn callable_methods(&self) -> HashMap<String, String> {
+        self.transport.callable_methods()
+    }
+
+    /// Enables recording of the `cap` most recently called state-setting
+    /// `(method, args)` pairs (methods named `set_*`), so `reconnect` can
+    /// replay them to restore on-device state. Recording is disabled (cap
+    /// 0) by default.
+    pub fn with_history(mut self, cap: usize) -> Self {
+        self.history_cap = cap;
+        self
+    }
+
+    /// Calls a method on the device with typed arguments and a typed result.
+    ///
+    /// Arguments are marshalled into native Python objects (ints, floats,
+    /// bools, lists, dicts - not just strings) and the Python layer returns
+    /// its result as a JSON string, which is deserialized into `R`. This
+    /// replaces the previous limitation that every argument had to be a
+    /// `&str` and every result an opaque Python-list `String`.
+    ///
+    /// If the transport reports a transient failure (e.g. a `DeviceError`/
+    /// timeout raised by the Python layer), this performs one automatic
+    /// `reconnect` and retries the call before giving up, so a single
+    /// dropped connection doesn't bubble up to the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The name of the method to be called.
+    /// * `args` - The method arguments, as JSON values.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(R)` containing the deserialized result if successful.
+    /// * `Err(MiioError)` if the transport call or the JSON conversion fails.
+    pub fn call<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<R, MiioError> {
+        match self.call_once(method, args) {
+            Ok(value) => {
+                self.record_history(method, args);
+                Ok(value)
+            }
+            Err(err) if self.transport.is_transient(&err) => {
+                self.reconnect(1)?;
+                let value = self.call_once(method, args)?;
+                self.record_history(method, args);
+                Ok(value)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Re-creates the underlying transport connection from `ip`/`token`/
+    /// `device_type`, then replays any recorded call history to restore
+    /// on-device state. Retries up to `attempts` times with a 1 second
+    /// backoff between attempts.
+    pub fn reconnect(&self, attempts: u32) -> Result<(), MiioError> {
+        let mut last_err = None;
+        for attempt in 0..attempts.max(1) {
+            if attempt > 0 {
+                std::thread::sleep(Duration::from_secs(1));
+            }
+            match self.transport.reconnect(&self.ip, &self.token, &self.device_type) {
+                Ok(()) => return self.replay_history(),
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err.expect("attempts.max(1) guarantees at least one attempt ran"))
+    }
+
+    /// Performs a single `call` without the automatic reconnect-and-retry,
+    /// used both as the inner call of `call` and to replay history without
+    /// re-recording it.
+    fn call_once<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<R, MiioError> {
+        let value = self.transport.call(method, args)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Replays the recorded call history against the (already reconnected)
+    /// transport.
+    fn replay_history(&self) -> Result<(), MiioError> {
+        let history: Vec<_> = self.history.lock().unwrap().iter().cloned().collect();
+        for (method, args) in history {
+            let _: serde_json::Value = self.call_once(&method, &args)?;
+        }
+        Ok(())
+    }
+
+    /// Appends `(method, args)` to the call history, evicting the oldest
+    /// entry once `history_cap` is reached. A no-op while history recording
+    /// is disabled (the default) or `method` isn't state-setting.
+    ///
+    /// Only `reconnect` needs history at all, to restore on-device state
+    /// (e.g. re-issuing `set_power`), so read-only calls (`info`, `status`,
+    /// ...) are filtered out by python-miio's own `set_*` naming convention
+    /// rather than recorded and needlessly replayed.
+    fn record_history(&self, method: &str, args: &[serde_json::Value]) {
+        if self.history_cap == 0 || !method.starts_with("set_") {
+            return;
+        }
+        let mut history = self.history.lock().unwrap();
+        if history.len() >= self.history_cap {
+            history.pop_front();
+        }
+        history.push_back((method.to_string(), args.to_vec()));
+    }
+
     /// Calls a method on the device by invoking the corresponding Python function.
     ///
-    /// This function sends a command to the device through Python and returns the result.
+    /// This is now a thin wrapper around [`Device::call`] that keeps the
+    /// original string-args signature for callers that haven't migrated yet.
     ///
     /// # Arguments
     ///
@@ -164,26 +333,16 @@ impl Device {
     ///
     /// # Returns
     ///
-    /// * `Ok(String)` containing the result if successful.
-    /// * `Err(PyErr)` if the Python call fails.
+    /// * `Ok(String)` containing the JSON-encoded result if successful.
+    /// * `Err(PyErr)` if the Python call or the JSON conversion fails.
     pub fn call_method(&self, method_name: &str, args: Vec<&str>) -> Result<String, PyErr> {
-        Python::with_gil(|py| {
-            // Import the Python module
-            let miio_module = PyModule::from_code(
-                py,
-                CString::new(MIIO_INTERFACE_CODE)?.as_c_str(),
-                &CString::new("miio_interface.py")?,
-                &CString::new("miio_interface")?,
-            )?;
-
-            // Retrieve the Python function 'call_method'
-            let call_method = miio_module.getattr("call_method")?;
-            // Call the function with arguments
-            let result: String = call_method
-                .call1((self.serialized_py_object.clone(), method_name, args))?
-                .extract()?;
-            Ok(result)
-        })
+        let args: Vec<serde_json::Value> = args
+            .into_iter()
+            .map(|arg| serde_json::Value::String(arg.to_string()))
+            .collect();
+        let result: serde_json::Value =
+            self.call(method_name, &args).map_err(MiioError::into_py_err)?;
+        Ok(result.to_string())
     }
 }
 
@@ -238,7 +397,7 @@ mod tests {
         assert_eq!(device.device_type, DEVICE_TYPE);
         assert_eq!(device.ip, IP);
         assert_eq!(device.token, TOKEN);
-        assert!(!device.serialized_py_object.is_empty());
+        assert!(!device.transport.state().is_empty());
     }
 
     #[test]
@@ -251,15 +410,52 @@ mod tests {
     #[test]
     fn test_get_device_methods() {
         let device = Device::create_device(IP, TOKEN, DEVICE_TYPE).unwrap();
-        assert!(!device.callable_methods.is_empty());
-        assert!(device.callable_methods.contains_key("toggle"));
+        assert!(!device.callable_methods().is_empty());
+        assert!(device.callable_methods().contains_key("toggle"));
     }
 
     #[test]
     fn test_call_method() {
         let device = Device::create_device(IP, TOKEN, DEVICE_TYPE).unwrap();
         let result = device.call_method(METHOD_NAME, vec![]).unwrap();
-        assert_eq!(result, "['ok']");
+        assert_eq!(result, "[\"ok\"]");
+    }
+
+    #[test]
+    fn test_call_typed() {
+        let device = Device::create_device(IP, TOKEN, DEVICE_TYPE).unwrap();
+        let result: Vec<String> = device.call(METHOD_NAME, &[]).unwrap();
+        assert_eq!(result, vec!["ok".to_string()]);
+    }
+
+    #[test]
+    fn test_create_device_with_explicit_transport() {
+        let device = Device::create_device_with::<PythonTransport>(IP, TOKEN, DEVICE_TYPE).unwrap();
+        assert!(!device.callable_methods().is_empty());
+    }
+
+    #[test]
+    fn test_reconnect() {
+        let device = Device::create_device(IP, TOKEN, DEVICE_TYPE).unwrap();
+        assert!(device.reconnect(1).is_ok());
+    }
+
+    #[test]
+    fn test_reconnect_replays_history() {
+        let device = Device::create_device(IP, TOKEN, DEVICE_TYPE)
+            .unwrap()
+            .with_history(4);
+        device.call_method(METHOD_NAME, vec![]).unwrap();
+        assert!(device.reconnect(1).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_call_async() {
+        let device = Device::create_device_async(IP, TOKEN, DEVICE_TYPE)
+            .await
+            .unwrap();
+        let result: Vec<String> = device.call_async(METHOD_NAME, &[]).await.unwrap();
+        assert_eq!(result, vec!["ok".to_string()]);
     }
 
     #[test]
@@ -284,13 +480,10 @@ mod tests {
         assert_eq!(device.device_type, deserialized_device.device_type);
         assert_eq!(device.ip, deserialized_device.ip);
         assert_eq!(device.token, deserialized_device.token);
+        assert_eq!(device.transport.state(), deserialized_device.transport.state());
         assert_eq!(
-            device.serialized_py_object,
-            deserialized_device.serialized_py_object
-        );
-        assert_eq!(
-            device.callable_methods,
-            deserialized_device.callable_methods
+            device.callable_methods(),
+            deserialized_device.callable_methods()
         );
     }
 }
@@ -0,0 +1,58 @@
+//! Experimental native (non-Python) transport.
+//!
+//! Gated behind the `unstable` feature: the miIO wire protocol isn't
+//! implemented here yet, this only proves out the `Transport` trait's
+//! shape for a future pure-Rust backend that talks to devices directly
+//! instead of shelling out to python-miio.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::transport::Transport;
+use crate::MiioError;
+
+/// Talks the miIO protocol directly, without an embedded Python interpreter.
+///
+/// Not implemented yet: every fallible method returns
+/// `MiioError::Unsupported`, `callable_methods`/`state` return empty. It
+/// exists so the `unstable` feature has a concrete second `Transport` to
+/// build against.
+#[derive(Clone, Default)]
+pub struct NativeTransport;
+
+impl NativeTransport {
+    fn unsupported() -> MiioError {
+        MiioError::Unsupported("NativeTransport does not implement the miIO wire protocol yet")
+    }
+}
+
+impl Transport for NativeTransport {
+    fn create(_ip: &str, _token: &str, _device_type: &str) -> Result<Self, MiioError> {
+        Err(Self::unsupported())
+    }
+
+    fn list_types() -> Result<Vec<String>, MiioError> {
+        Err(Self::unsupported())
+    }
+
+    fn call(&self, _method: &str, _args: &[Value]) -> Result<Value, MiioError> {
+        Err(Self::unsupported())
+    }
+
+    fn callable_methods(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    fn reconnect(&self, _ip: &str, _token: &str, _device_type: &str) -> Result<(), MiioError> {
+        Err(Self::unsupported())
+    }
+
+    fn state(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn clone_box(&self) -> Box<dyn Transport> {
+        Box::new(self.clone())
+    }
+}
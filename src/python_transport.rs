@@ -0,0 +1,192 @@
+//! The stable `Transport` implementation, backed by the embedded Python
+//! interpreter.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use pyo3::prelude::*;
+use pyo3::types::{PyAny, PyBytes, PyDict, PyModule};
+
+use serde_json::Value;
+
+use crate::transport::Transport;
+use crate::{MiioError, MiioRuntime};
+
+/// Talks to a device through the embedded python-miio interpreter.
+///
+/// The only stable `Transport` implementation: everything `Device` did
+/// before the `Transport` abstraction existed lives here unchanged.
+#[derive(Clone)]
+pub struct PythonTransport {
+    /// A serialized (pickled) representation of the underlying Python
+    /// object, used to persist the handle and to rebuild the live object on
+    /// demand. Wrapped in a `Mutex` because `reconnect` refreshes it in
+    /// place, so `state()`/`Device` persistence reflect the reconnected
+    /// object rather than a stale pre-reconnect snapshot.
+    serialized_py_object: Arc<Mutex<Vec<u8>>>,
+    /// The live Python device object, unpickled from `serialized_py_object`
+    /// lazily on first use and cached so repeated calls don't pay for
+    /// re-unpickling.
+    live_object: Arc<Mutex<Option<Py<PyAny>>>>,
+    /// A map of callable method names to their corresponding Python signatures.
+    callable_methods: HashMap<String, String>,
+}
+
+impl PythonTransport {
+    /// Rebuilds a `PythonTransport` from previously persisted state, e.g.
+    /// via `Device::deserialize_from_file`. The live object is rebuilt
+    /// lazily on first use, exactly like a freshly created transport.
+    pub(crate) fn from_state(
+        serialized_py_object: Vec<u8>,
+        callable_methods: HashMap<String, String>,
+    ) -> Self {
+        PythonTransport {
+            serialized_py_object: Arc::new(Mutex::new(serialized_py_object)),
+            callable_methods,
+            live_object: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the cached live Python device object, unpickling
+    /// `serialized_py_object` on first use via the module's
+    /// `deserialize_device` function.
+    fn live_python_object<'py>(
+        &self,
+        py: Python<'py>,
+        module: &Bound<'py, PyModule>,
+    ) -> Result<Bound<'py, PyAny>, PyErr> {
+        let mut cache = self.live_object.lock().unwrap();
+        if let Some(object) = cache.as_ref() {
+            return Ok(object.bind(py).clone());
+        }
+
+        let deserialize_device = module.getattr("deserialize_device")?;
+        let state = self.serialized_py_object.lock().unwrap();
+        let bytes = PyBytes::new(py, &state);
+        let object = deserialize_device.call1((bytes,))?;
+        *cache = Some(object.clone().unbind());
+        Ok(object)
+    }
+
+    /// Maps a miIO model id (as returned by a device's `info()` call) to
+    /// the `device_type` class name `Device::create_device` expects,
+    /// e.g. `"yeelink.light.color1"` -> `"Yeelight"`. Used by
+    /// `DeviceBuilder::resolve_device_type` to auto-resolve discovered
+    /// devices.
+    pub(crate) fn resolve_device_type(model: &str) -> Result<Option<String>, MiioError> {
+        MiioRuntime::global()?
+            .with_module(|_py, module| {
+                let resolve_device_type = module.getattr("resolve_device_type")?;
+                let device_type: Option<String> = resolve_device_type.call1((model,))?.extract()?;
+                Ok(device_type)
+            })
+            .map_err(MiioError::from)
+    }
+}
+
+impl Transport for PythonTransport {
+    fn create(ip: &str, token: &str, device_type: &str) -> Result<Self, MiioError> {
+        MiioRuntime::global()?
+            .with_module(|_py, module| {
+                // Retrieve the Python function 'get_device'
+                let create_device = module.getattr("get_device")?;
+                // Call the function with arguments
+                let device: Bound<'_, PyBytes> = create_device
+                    .call1((ip, token, device_type))?
+                    .downcast::<PyBytes>()?
+                    .clone();
+
+                // Retrieve the Python function 'get_device_methods'
+                let get_device_methods = module.getattr("get_device_methods")?;
+                // Call the function with arguments
+                let methods = get_device_methods.call1((device.clone(),))?; // Dict returned
+                let methods = methods.downcast::<PyDict>()?;
+                let mut callable_methods = HashMap::new();
+                for (key, value) in methods.iter() {
+                    let key = key.extract::<String>()?;
+                    let value = value.extract::<String>()?;
+                    callable_methods.insert(key, value);
+                }
+
+                Ok(PythonTransport {
+                    serialized_py_object: Arc::new(Mutex::new(device.as_bytes().to_vec())),
+                    callable_methods,
+                    live_object: Arc::new(Mutex::new(None)),
+                })
+            })
+            .map_err(MiioError::from)
+    }
+
+    fn list_types() -> Result<Vec<String>, MiioError> {
+        MiioRuntime::global()?
+            .with_module(|_py, module| {
+                // Retrieve the Python function 'get_device_types'
+                let get_device_types = module.getattr("get_device_types")?;
+                // Call the function without arguments
+                let device_types_py = get_device_types.call0()?;
+                // Convert Python list to Rust Vec<String>
+                let v: Vec<String> = device_types_py.extract()?;
+                Ok(v)
+            })
+            .map_err(MiioError::from)
+    }
+
+    fn call(&self, method: &str, args: &[Value]) -> Result<Value, MiioError> {
+        let args_json = serde_json::to_string(args)?;
+        let result_json = MiioRuntime::global()?.with_module(|py, module| {
+            let live_object = self.live_python_object(py, module)?;
+            // Retrieve the Python function 'call_method_json', which marshals
+            // `args_json` into native Python objects and returns the result
+            // via `json.dumps`.
+            let call_method_json = module.getattr("call_method_json")?;
+            let result: String = call_method_json
+                .call1((live_object, method, args_json.as_str()))?
+                .extract()?;
+            Ok(result)
+        })?;
+        Ok(serde_json::from_str(&result_json)?)
+    }
+
+    fn callable_methods(&self) -> HashMap<String, String> {
+        self.callable_methods.clone()
+    }
+
+    fn reconnect(&self, ip: &str, token: &str, device_type: &str) -> Result<(), MiioError> {
+        MiioRuntime::global()?
+            .with_module(|_py, module| {
+                let get_device = module.getattr("get_device")?;
+                let bytes: Bound<'_, PyBytes> = get_device
+                    .call1((ip, token, device_type))?
+                    .downcast::<PyBytes>()?
+                    .clone();
+                let deserialize_device = module.getattr("deserialize_device")?;
+                let live_object = deserialize_device.call1((bytes.clone(),))?;
+                *self.live_object.lock().unwrap() = Some(live_object.unbind());
+                *self.serialized_py_object.lock().unwrap() = bytes.as_bytes().to_vec();
+                Ok(())
+            })
+            .map_err(MiioError::from)
+    }
+
+    fn state(&self) -> Vec<u8> {
+        self.serialized_py_object.lock().unwrap().clone()
+    }
+
+    fn is_transient(&self, err: &MiioError) -> bool {
+        let MiioError::Python(py_err) = err else {
+            return false;
+        };
+        Python::with_gil(|py| {
+            let name = py_err
+                .get_type(py)
+                .name()
+                .map(|n| n.to_string())
+                .unwrap_or_default();
+            name == "DeviceError" || name == "TimeoutError"
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn Transport> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,63 @@
+//! Persistent embedded Python interpreter.
+//!
+//! Every device call used to `PyModule::from_code` the full
+//! `miio_interface.py` source under a fresh `Python::with_gil`, recompiling
+//! the module on each invocation. `MiioRuntime` imports it on first use and
+//! hands out a cached `Py<PyModule>` that every call reuses.
+
+use std::ffi::CString;
+use std::sync::{Arc, OnceLock};
+
+use pyo3::prelude::*;
+use pyo3::types::PyModule;
+
+use crate::MIIO_INTERFACE_CODE;
+
+/// A persistent handle to the compiled `miio_interface` module.
+///
+/// Cheap to clone: the underlying module is compiled once and shared behind
+/// an `Arc`, so every `Device` can hold (or borrow) a runtime without paying
+/// for recompilation.
+#[derive(Clone)]
+pub struct MiioRuntime {
+    module: Arc<Py<PyModule>>,
+}
+
+impl MiioRuntime {
+    /// Imports and compiles `miio_interface` once.
+    fn new() -> Result<Self, PyErr> {
+        let module = Python::with_gil(|py| -> Result<Py<PyModule>, PyErr> {
+            let module = PyModule::from_code(
+                py,
+                CString::new(MIIO_INTERFACE_CODE)?.as_c_str(),
+                &CString::new("miio_interface.py")?,
+                &CString::new("miio_interface")?,
+            )?;
+            Ok(module.unbind())
+        })?;
+        Ok(MiioRuntime {
+            module: Arc::new(module),
+        })
+    }
+
+    /// Returns the process-wide runtime, compiling `miio_interface` on the
+    /// first call and reusing the cached module on every later one. Threads
+    /// whose first calls overlap may each compile the module; only the first
+    /// result stored is kept and shared.
+    pub fn global() -> Result<&'static MiioRuntime, PyErr> {
+        static RUNTIME: OnceLock<MiioRuntime> = OnceLock::new();
+        if let Some(runtime) = RUNTIME.get() {
+            return Ok(runtime);
+        }
+        let runtime = MiioRuntime::new()?;
+        Ok(RUNTIME.get_or_init(|| runtime))
+    }
+
+    /// Runs `f` with the cached module bound to the current GIL token.
+    pub(crate) fn with_module<R>(
+        &self,
+        f: impl FnOnce(Python<'_>, &Bound<'_, PyModule>) -> Result<R, PyErr>,
+    ) -> Result<R, PyErr> {
+        Python::with_gil(|py| f(py, self.module.bind(py)))
+    }
+}
@@ -0,0 +1,65 @@
+//! Pluggable transport abstraction so `Device` isn't fused to the embedded
+//! Python interpreter.
+//!
+//! `Transport` captures what a device backend must support: creating a
+//! handle, listing the device types it knows about, and invoking a method on
+//! an already-created handle. `PythonTransport` is the only stable
+//! implementation, going through the embedded interpreter exactly as before.
+//! Swapping in a different backend - gated behind the `unstable` feature -
+//! doesn't require touching `Device`'s call sites.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::MiioError;
+
+/// A backend capable of creating, listing and calling Miio devices.
+///
+/// `create`/`list_types` build a concrete `Self` and so aren't callable
+/// through `dyn Transport`; `Device` picks its backend via
+/// `Device::create_device_with::<T>` and boxes the result.
+pub trait Transport: Send + Sync {
+    /// Creates a device handle for `ip`/`token`/`device_type`.
+    fn create(ip: &str, token: &str, device_type: &str) -> Result<Self, MiioError>
+    where
+        Self: Sized;
+
+    /// Lists the device types this backend knows how to create.
+    fn list_types() -> Result<Vec<String>, MiioError>
+    where
+        Self: Sized;
+
+    /// Calls `method` on this handle with JSON-encoded `args`, returning its
+    /// JSON-decoded result.
+    fn call(&self, method: &str, args: &[Value]) -> Result<Value, MiioError>;
+
+    /// The callable methods this handle exposes.
+    fn callable_methods(&self) -> HashMap<String, String>;
+
+    /// Re-creates the handle's underlying connection from `ip`/`token`/
+    /// `device_type`, e.g. after a dropped network connection. Implementors
+    /// should also refresh whatever `state()` returns, so persistence after
+    /// a successful reconnect doesn't write a stale pre-reconnect snapshot.
+    fn reconnect(&self, ip: &str, token: &str, device_type: &str) -> Result<(), MiioError>;
+
+    /// Opaque serialized state, persisted by `Device::serialize_to_file` and
+    /// handed back to a backend-specific constructor on load.
+    fn state(&self) -> Vec<u8>;
+
+    /// Whether `err` represents a transient failure worth a single
+    /// reconnect-and-retry. Defaults to `false`.
+    fn is_transient(&self, _err: &MiioError) -> bool {
+        false
+    }
+
+    /// Clones this handle behind a fresh `Box<dyn Transport>`, since `dyn
+    /// Transport` can't derive `Clone` directly.
+    fn clone_box(&self) -> Box<dyn Transport>;
+}
+
+impl Clone for Box<dyn Transport> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}